@@ -0,0 +1,86 @@
+//! Throughput benchmarks for key-schedule generation and per-block
+//! encode/decode, across each supported word size. Run with `cargo bench`.
+//!
+//! Key-schedule generation, the streaming path and the block encode/decode
+//! path are timed as separate groups, since they have very different cost
+//! profiles: schedule generation runs once per key, `Rc5Stream` amortizes it
+//! over many blocks, and the block groups time `Rc5Cipher::encode`/`decode`,
+//! which reuse a schedule built once up front.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rc5::{Rc5Cipher, Rc5Stream};
+
+const KEY: [u8; 16] = [
+    0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D, 0x0E, 0x0F,
+];
+
+fn bench_key_schedule(c: &mut Criterion) {
+    let mut group = c.benchmark_group("key_schedule");
+    group.bench_function("u16", |b| {
+        b.iter(|| Rc5Cipher::<u16>::new(KEY.to_vec(), 12).unwrap())
+    });
+    group.bench_function("u32", |b| {
+        b.iter(|| Rc5Cipher::<u32>::new(KEY.to_vec(), 12).unwrap())
+    });
+    group.bench_function("u64", |b| {
+        b.iter(|| Rc5Cipher::<u64>::new(KEY.to_vec(), 12).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_encode_block(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_block");
+    let cipher16 = Rc5Cipher::<u16>::new(KEY.to_vec(), 12).unwrap();
+    let cipher32 = Rc5Cipher::<u32>::new(KEY.to_vec(), 12).unwrap();
+    let cipher64 = Rc5Cipher::<u64>::new(KEY.to_vec(), 12).unwrap();
+    let pt16 = [0u8; 4];
+    let pt32 = [0u8; 8];
+    let pt64 = [0u8; 16];
+    group.bench_function("u16", |b| {
+        b.iter(|| cipher16.encode(black_box(&pt16)).unwrap())
+    });
+    group.bench_function("u32", |b| {
+        b.iter(|| cipher32.encode(black_box(&pt32)).unwrap())
+    });
+    group.bench_function("u64", |b| {
+        b.iter(|| cipher64.encode(black_box(&pt64)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_decode_block(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_block");
+    let cipher16 = Rc5Cipher::<u16>::new(KEY.to_vec(), 12).unwrap();
+    let cipher32 = Rc5Cipher::<u32>::new(KEY.to_vec(), 12).unwrap();
+    let cipher64 = Rc5Cipher::<u64>::new(KEY.to_vec(), 12).unwrap();
+    let ct16 = cipher16.encode(&[0u8; 4]).unwrap();
+    let ct32 = cipher32.encode(&[0u8; 8]).unwrap();
+    let ct64 = cipher64.encode(&[0u8; 16]).unwrap();
+    group.bench_function("u16", |b| {
+        b.iter(|| cipher16.decode(black_box(&ct16)).unwrap())
+    });
+    group.bench_function("u32", |b| {
+        b.iter(|| cipher32.decode(black_box(&ct32)).unwrap())
+    });
+    group.bench_function("u64", |b| {
+        b.iter(|| cipher64.decode(black_box(&ct64)).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_stream(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stream_update");
+    let data = vec![0u8; 8 * 1024];
+    let mut stream = Rc5Stream::<u32>::encoder(KEY.to_vec(), 12).unwrap();
+    group.bench_function("u32", |b| b.iter(|| black_box(stream.update(&data))));
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_key_schedule,
+    bench_encode_block,
+    bench_decode_block,
+    bench_stream
+);
+criterion_main!(benches);
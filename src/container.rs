@@ -0,0 +1,228 @@
+//! A self-describing ciphertext envelope.
+//!
+//! The container prepends a small header (word size in bits and round
+//! count as unsigned LEB128, plus a one-byte padding tag) to the block
+//! data, so a decoder doesn't need to be told out of band which `Word`
+//! width, round count or padding scheme produced it. [`decode_self_describing`]
+//! runs the recovered [`Padding`] through [`padding::unpad`] to recover the
+//! original, pre-padding plaintext, so a tampered trailer is rejected
+//! rather than blindly trusted.
+
+use crate::{decode_with, encode_with, padding, Padding, Rc5Error, WordSize};
+
+fn write_leb128(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let mut byte = (value & 0x7F) as u8;
+        value >>= 7;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        out.push(byte);
+        if value == 0 {
+            break;
+        }
+    }
+}
+
+/// How many bytes a `u64` field can ever legitimately need
+/// (`ceil(64 / 7)`). Bounds [`read_leb128`] against an unterminated run of
+/// continuation-flagged bytes, which would otherwise shift `shift` past 63.
+const MAX_LEB128_BYTES: usize = 10;
+
+fn read_leb128(bytes: &[u8]) -> Result<(u64, usize), Rc5Error> {
+    let mut result: u64 = 0;
+    let mut shift = 0;
+    for (consumed, &byte) in bytes.iter().enumerate() {
+        if consumed >= MAX_LEB128_BYTES {
+            return Err(Rc5Error::MalformedHeader);
+        }
+        result |= ((byte & 0x7F) as u64) << shift;
+        shift += 7;
+        if byte & 0x80 == 0 {
+            return Ok((result, consumed + 1));
+        }
+    }
+    Err(Rc5Error::TruncatedHeader)
+}
+
+fn bits_of(word: WordSize) -> u64 {
+    match word {
+        WordSize::W16 => 16,
+        WordSize::W32 => 32,
+        WordSize::W64 => 64,
+    }
+}
+
+fn word_of_bits(bits: u64) -> Option<WordSize> {
+    match bits {
+        16 => Some(WordSize::W16),
+        32 => Some(WordSize::W32),
+        64 => Some(WordSize::W64),
+        _ => None,
+    }
+}
+
+/// Bytes in a whole block (two words) of `word`, matching `compute_into`'s
+/// `W::zero().count_zeros() / 4`.
+fn block_bytes_of(word: WordSize) -> usize {
+    bits_of(word) as usize / 4
+}
+
+fn tag_of_padding(padding: Padding) -> u8 {
+    match padding {
+        Padding::None => 0,
+        Padding::Pkcs7 => 1,
+    }
+}
+
+fn padding_of_tag(tag: u8) -> Option<Padding> {
+    match tag {
+        0 => Some(Padding::None),
+        1 => Some(Padding::Pkcs7),
+        _ => None,
+    }
+}
+
+/// Encode `plaintext` and prepend a header describing `word`, `rounds` and
+/// `padding`, so [`decode_self_describing`] can recover them. `padding` is
+/// applied before encoding, so `plaintext` doesn't need to already be a
+/// whole number of blocks.
+pub fn encode_self_describing(
+    word: WordSize,
+    rounds: u8,
+    padding: Padding,
+    key: Vec<u8>,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, Rc5Error> {
+    let mut out = Vec::new();
+    write_leb128(bits_of(word), &mut out);
+    write_leb128(rounds as u64, &mut out);
+    out.push(tag_of_padding(padding));
+    let padded = padding::pad(plaintext, block_bytes_of(word), padding);
+    let body = match word {
+        WordSize::W16 => encode_with::<u16>(key, rounds, &padded)?,
+        WordSize::W32 => encode_with::<u32>(key, rounds, &padded)?,
+        WordSize::W64 => encode_with::<u64>(key, rounds, &padded)?,
+    };
+    out.extend(body);
+    Ok(out)
+}
+
+/// Parse the header off `container` and decode the remaining block data
+/// with the `key` and recovered word size/round count, then run the
+/// recovered `padding` through [`padding::unpad`] to validate the trailer
+/// and recover the original, pre-padding plaintext.
+pub fn decode_self_describing(key: Vec<u8>, container: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+    let (bits, n1) = read_leb128(container)?;
+    let (rounds, n2) = read_leb128(&container[n1..])?;
+    let rounds = u8::try_from(rounds).map_err(|_| Rc5Error::MalformedHeader)?;
+    let &tag = container.get(n1 + n2).ok_or(Rc5Error::TruncatedHeader)?;
+    let padding = padding_of_tag(tag).ok_or(Rc5Error::MalformedHeader)?;
+    let word = word_of_bits(bits).ok_or(Rc5Error::UnknownWordSize { bits })?;
+    let body = &container[n1 + n2 + 1..];
+
+    let plaintext = match word {
+        WordSize::W16 => decode_with::<u16>(key, rounds, body)?,
+        WordSize::W32 => decode_with::<u32>(key, rounds, body)?,
+        WordSize::W64 => decode_with::<u64>(key, rounds, body)?,
+    };
+    padding::unpad(plaintext, padding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn leb128_round_trip() {
+        for value in [0u64, 1, 127, 128, 300, 16384, u64::MAX] {
+            let mut buf = Vec::new();
+            write_leb128(value, &mut buf);
+            let (decoded, consumed) = read_leb128(&buf).unwrap();
+            assert_eq!(decoded, value);
+            assert_eq!(consumed, buf.len());
+        }
+    }
+
+    #[test]
+    fn read_leb128_rejects_unterminated_run() {
+        assert_eq!(read_leb128(&[0x80; 11]), Err(Rc5Error::MalformedHeader));
+    }
+
+    #[test]
+    fn round_trip_u32() {
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let container =
+            encode_self_describing(WordSize::W32, 12, Padding::None, key.clone(), &pt).unwrap();
+        let res = decode_self_describing(key, &container).unwrap();
+        assert!(pt[..] == res[..]);
+    }
+
+    #[test]
+    fn round_trip_u16_custom_rounds() {
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let pt = vec![0x00, 0x11, 0x22, 0x33];
+        let container =
+            encode_self_describing(WordSize::W16, 20, Padding::None, key.clone(), &pt).unwrap();
+        let res = decode_self_describing(key, &container).unwrap();
+        assert!(pt[..] == res[..]);
+    }
+
+    #[test]
+    fn round_trip_pkcs7_unaligned_plaintext() {
+        let key = vec![0u8; 16];
+        let pt = vec![0x00, 0x11, 0x22];
+        let container =
+            encode_self_describing(WordSize::W32, 12, Padding::Pkcs7, key.clone(), &pt).unwrap();
+        let res = decode_self_describing(key, &container).unwrap();
+        assert_eq!(res, pt);
+    }
+
+    #[test]
+    fn decode_rejects_truncated_header() {
+        assert_eq!(
+            decode_self_describing(vec![0u8; 16], &[0x80]),
+            Err(Rc5Error::TruncatedHeader)
+        );
+    }
+
+    #[test]
+    fn decode_rejects_unterminated_header_field_instead_of_panicking() {
+        assert_eq!(
+            decode_self_describing(vec![0u8; 16], &[0x80; 11]),
+            Err(Rc5Error::MalformedHeader)
+        );
+    }
+
+    #[test]
+    fn decode_rejects_rounds_overflowing_u8_instead_of_wrapping() {
+        let mut container = Vec::new();
+        write_leb128(bits_of(WordSize::W32), &mut container);
+        write_leb128(256, &mut container);
+        container.push(tag_of_padding(Padding::None));
+        assert_eq!(
+            decode_self_describing(vec![0u8; 16], &container),
+            Err(Rc5Error::MalformedHeader)
+        );
+    }
+
+    #[test]
+    fn decode_rejects_tampered_pkcs7_trailer_instead_of_returning_garbage() {
+        let key = vec![0u8; 16];
+        let pt = vec![0x00, 0x11, 0x22];
+        let mut container =
+            encode_self_describing(WordSize::W32, 12, Padding::Pkcs7, key.clone(), &pt).unwrap();
+        *container.last_mut().unwrap() ^= 0x01;
+        assert_eq!(
+            decode_self_describing(key, &container),
+            Err(Rc5Error::InvalidPadding)
+        );
+    }
+}
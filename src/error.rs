@@ -0,0 +1,48 @@
+//! Error type returned by the fallible encode/decode entry points, instead
+//! of panicking on bad input.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rc5Error {
+    /// The key was longer than the 255 bytes the key schedule allows.
+    KeyTooLong { len: usize },
+    /// The input wasn't a whole number of `expected`-byte blocks.
+    InvalidBlockLength { expected: usize, got: usize },
+    /// A self-describing container's LEB128 header was missing or cut off.
+    TruncatedHeader,
+    /// A self-describing container's header had an invalid field: a LEB128
+    /// field that ran past the bytes a `u64` can encode instead of ever
+    /// terminating, a round count above 255, or an unknown padding tag.
+    MalformedHeader,
+    /// A self-describing container declared a word size no `Word` impl handles.
+    UnknownWordSize { bits: u64 },
+    /// The PKCS#7 padding on a decoded block was missing or malformed.
+    InvalidPadding,
+}
+
+impl fmt::Display for Rc5Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rc5Error::KeyTooLong { len } => {
+                write!(f, "key should be 0 to 255 bytes long, got {len}")
+            }
+            Rc5Error::InvalidBlockLength { expected, got } => write!(
+                f,
+                "input should be divisible into {expected} byte blocks, got {got} bytes"
+            ),
+            Rc5Error::TruncatedHeader => {
+                write!(f, "self-describing container header is truncated")
+            }
+            Rc5Error::MalformedHeader => {
+                write!(f, "self-describing container header field is malformed")
+            }
+            Rc5Error::UnknownWordSize { bits } => {
+                write!(f, "no Word implementation for a {bits}-bit word")
+            }
+            Rc5Error::InvalidPadding => write!(f, "PKCS#7 padding is missing or malformed"),
+        }
+    }
+}
+
+impl std::error::Error for Rc5Error {}
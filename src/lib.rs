@@ -5,6 +5,16 @@ use num::{
     Integer, PrimInt,
 };
 
+mod container;
+mod error;
+mod padding;
+mod stream;
+
+pub use container::{decode_self_describing, encode_self_describing};
+pub use error::Rc5Error;
+pub use padding::Padding;
+pub use stream::{FlushPadding, Rc5Stream};
+
 pub trait MagicConstants {
     fn p() -> Self;
     fn q() -> Self;
@@ -45,6 +55,14 @@ pub trait FromLeBytes<'a> {
     fn from_le_bytes(bytes: Self::Bytes) -> Self;
 }
 
+impl<'a> FromLeBytes<'a> for u16 {
+    type Bytes = [u8; Self::BITS as usize / 8];
+
+    fn from_le_bytes(bytes: Self::Bytes) -> Self {
+        Self::from_le_bytes(bytes)
+    }
+}
+
 impl<'a> FromLeBytes<'a> for u32 {
     type Bytes = [u8; Self::BITS as usize / 8];
 
@@ -53,12 +71,28 @@ impl<'a> FromLeBytes<'a> for u32 {
     }
 }
 
+impl<'a> FromLeBytes<'a> for u64 {
+    type Bytes = [u8; Self::BITS as usize / 8];
+
+    fn from_le_bytes(bytes: Self::Bytes) -> Self {
+        Self::from_le_bytes(bytes)
+    }
+}
+
 pub trait ToLeBytes<'a> {
     type Bytes: AsRef<[u8]>;
 
     fn to_le_bytes(num: Self) -> Self::Bytes;
 }
 
+impl<'a> ToLeBytes<'a> for u16 {
+    type Bytes = [u8; 2];
+
+    fn to_le_bytes(num: Self) -> Self::Bytes {
+        Self::to_le_bytes(num)
+    }
+}
+
 impl<'a> ToLeBytes<'a> for u32 {
     type Bytes = [u8; 4];
 
@@ -67,6 +101,14 @@ impl<'a> ToLeBytes<'a> for u32 {
     }
 }
 
+impl<'a> ToLeBytes<'a> for u64 {
+    type Bytes = [u8; 8];
+
+    fn to_le_bytes(num: Self) -> Self::Bytes {
+        Self::to_le_bytes(num)
+    }
+}
+
 pub trait Word<'a>:
     PrimInt + Integer + WrappingAdd + WrappingSub + FromLeBytes<'a> + ToLeBytes<'a> + MagicConstants
 where
@@ -89,11 +131,13 @@ where
 {
 }
 
-fn gen_key_table<'a, W: Word<'a>>(key: Vec<u8>, rounds: u8) -> Vec<W>
+pub(crate) fn gen_key_table<'a, W: Word<'a>>(key: Vec<u8>, rounds: u8) -> Result<Vec<W>, Rc5Error>
 where
     <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
 {
-    assert!(key.len() <= 255, "key should be 0 to 255 bytes long");
+    if key.len() > 255 {
+        return Err(Rc5Error::KeyTooLong { len: key.len() });
+    }
     let t = 2 * rounds as usize + 2;
     let mut s = vec![W::zero(); t];
     let w = W::zero().count_zeros() as usize;
@@ -124,38 +168,100 @@ where
         i = (i + 1) % t;
         j = (j + 1) % c;
     }
-    s
+    Ok(s)
 }
 
-type TranscodeFn<W> = fn((W, W), &[W]) -> (W, W);
+pub(crate) type TranscodeFn<W> = fn((W, W), &[W]) -> (W, W);
 
-fn compute<'a, W: Word<'a>>(input: &'a [u8], key_table: Vec<W>, fun: TranscodeFn<W>) -> Vec<u8>
+/// Split one block into its two words, run `fun` over them, and serialize
+/// the result back to bytes. Used by `Rc5Stream`.
+pub(crate) fn transcode_block<'a, W: Word<'a>>(
+    block: &'a [u8],
+    key_table: &[W],
+    fun: TranscodeFn<W>,
+) -> Vec<u8>
 where
     <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
 {
-    let block_bytes = W::zero().count_zeros() as usize / 4;
-    assert!(
-        input.len() % block_bytes == 0,
-        "input should be divisible into {} byte blocks",
-        block_bytes
+    let (first, second) = block.split_at(block.len() / 2);
+    let i0 =
+        W::from_le_bytes(<W as FromLeBytes>::Bytes::try_from(first).expect("w == W::Bytes::len()"));
+    let i1 = W::from_le_bytes(
+        <W as FromLeBytes>::Bytes::try_from(second).expect("w == W::Bytes::len()"),
     );
-    let mut output = Vec::new();
-    for iblock in input.chunks_exact(block_bytes) {
-        let (first, second) = iblock.split_at(block_bytes / 2);
-        let i0 = W::from_le_bytes(
-            <W as FromLeBytes>::Bytes::try_from(first).expect("w == W::Bytes::len()"),
-        );
-        let i1 = W::from_le_bytes(
-            <W as FromLeBytes>::Bytes::try_from(second).expect("w == W::Bytes::len()"),
-        );
-        let (o0, o1) = fun((i0, i1), &key_table);
-        output.extend(W::to_le_bytes(o0).as_ref());
-        output.extend(W::to_le_bytes(o1).as_ref());
-    }
+    let (o0, o1) = fun((i0, i1), key_table);
+    let mut output = Vec::with_capacity(block.len());
+    output.extend(W::to_le_bytes(o0).as_ref());
+    output.extend(W::to_le_bytes(o1).as_ref());
     output
 }
 
-fn encode_block<'a, W: Word<'a>>(plaintext: (W, W), key_table: &[W]) -> (W, W)
+/// Split one block into its two words, run `fun` over them, and serialize
+/// the result directly into `out` (which must be exactly `block.len()`
+/// bytes long). Like [`transcode_block`], but without the per-block `Vec`.
+pub(crate) fn transcode_block_into<'a, W: Word<'a>>(
+    block: &'a [u8],
+    key_table: &[W],
+    fun: TranscodeFn<W>,
+    out: &mut [u8],
+) where
+    <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    let (first, second) = block.split_at(block.len() / 2);
+    let i0 =
+        W::from_le_bytes(<W as FromLeBytes>::Bytes::try_from(first).expect("w == W::Bytes::len()"));
+    let i1 = W::from_le_bytes(
+        <W as FromLeBytes>::Bytes::try_from(second).expect("w == W::Bytes::len()"),
+    );
+    let (o0, o1) = fun((i0, i1), key_table);
+    let (ofirst, osecond) = out.split_at_mut(block.len() / 2);
+    ofirst.copy_from_slice(W::to_le_bytes(o0).as_ref());
+    osecond.copy_from_slice(W::to_le_bytes(o1).as_ref());
+}
+
+/// Transcodes `input` block by block straight into the caller-supplied
+/// `output` buffer, without allocating per block. `compute` is this over a
+/// freshly allocated buffer. `output` must be the same length as `input`.
+pub(crate) fn compute_into<'a, W: Word<'a>>(
+    output: &mut [u8],
+    input: &'a [u8],
+    key_table: &[W],
+    fun: TranscodeFn<W>,
+) -> Result<(), Rc5Error>
+where
+    <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    let block_bytes = W::zero().count_zeros() as usize / 4;
+    if !input.len().is_multiple_of(block_bytes) {
+        return Err(Rc5Error::InvalidBlockLength {
+            expected: block_bytes,
+            got: input.len(),
+        });
+    }
+    debug_assert_eq!(output.len(), input.len());
+    for (iblock, oblock) in input
+        .chunks_exact(block_bytes)
+        .zip(output.chunks_exact_mut(block_bytes))
+    {
+        transcode_block_into(iblock, key_table, fun, oblock);
+    }
+    Ok(())
+}
+
+fn compute<'a, W: Word<'a>>(
+    input: &'a [u8],
+    key_table: Vec<W>,
+    fun: TranscodeFn<W>,
+) -> Result<Vec<u8>, Rc5Error>
+where
+    <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    let mut output = vec![0u8; input.len()];
+    compute_into(&mut output, input, &key_table, fun)?;
+    Ok(output)
+}
+
+pub(crate) fn encode_block<'a, W: Word<'a>>(plaintext: (W, W), key_table: &[W]) -> (W, W)
 where
     <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
 {
@@ -177,18 +283,127 @@ where
     (a, b)
 }
 
+/// The RC5 word size, i.e. which concrete `Word` implementation a set of
+/// [`Rc5Params`] should be run with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordSize {
+    W16,
+    W32,
+    W64,
+}
+
+/// The full RC5-w/r/b parameterization: word size, round count and key.
+///
+/// `encode`/`decode` hard-code the canonical RC5-32/12 choice; `Rc5Params`
+/// lets callers pick lighter or heavier variants (e.g. RC5-32/20) instead.
+#[derive(Debug, Clone)]
+pub struct Rc5Params {
+    pub word: WordSize,
+    pub rounds: u8,
+    pub key: Vec<u8>,
+}
+
+impl Rc5Params {
+    pub fn encode(&self, plaintext: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        match self.word {
+            WordSize::W16 => encode_with::<u16>(self.key.clone(), self.rounds, plaintext),
+            WordSize::W32 => encode_with::<u32>(self.key.clone(), self.rounds, plaintext),
+            WordSize::W64 => encode_with::<u64>(self.key.clone(), self.rounds, plaintext),
+        }
+    }
+
+    pub fn decode(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Rc5Error> {
+        match self.word {
+            WordSize::W16 => decode_with::<u16>(self.key.clone(), self.rounds, ciphertext),
+            WordSize::W32 => decode_with::<u32>(self.key.clone(), self.rounds, ciphertext),
+            WordSize::W64 => decode_with::<u64>(self.key.clone(), self.rounds, ciphertext),
+        }
+    }
+}
+
+/// Caches the key schedule computed from a key and round count, so repeated
+/// [`encode`](Rc5Cipher::encode)/[`decode`](Rc5Cipher::decode) calls reuse
+/// it instead of regenerating it from the key every time.
+pub struct Rc5Cipher<W> {
+    key_table: Vec<W>,
+}
+
+impl<W> Rc5Cipher<W> {
+    pub fn new<'a>(key: Vec<u8>, rounds: u8) -> Result<Self, Rc5Error>
+    where
+        W: Word<'a>,
+        <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        Ok(Self {
+            key_table: gen_key_table(key, rounds)?,
+        })
+    }
+
+    pub fn encode(&self, plaintext: &[u8]) -> Result<Vec<u8>, Rc5Error>
+    where
+        W: for<'a> Word<'a>,
+        for<'a> <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        let mut ciphertext = vec![0u8; plaintext.len()];
+        compute_into(&mut ciphertext, plaintext, &self.key_table, encode_block)?;
+        Ok(ciphertext)
+    }
+
+    pub fn decode(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Rc5Error>
+    where
+        W: for<'a> Word<'a>,
+        for<'a> <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        let mut plaintext = vec![0u8; ciphertext.len()];
+        compute_into(&mut plaintext, ciphertext, &self.key_table, decode_block)?;
+        Ok(plaintext)
+    }
+}
+
+/*
+ * This function should return a cipher text for a given key, round count and plaintext
+ *
+ */
+pub fn encode_with<'a, W: Word<'a>>(
+    key: Vec<u8>,
+    rounds: u8,
+    plaintext: &'a [u8],
+) -> Result<Vec<u8>, Rc5Error>
+where
+    <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    compute::<W>(plaintext, gen_key_table(key, rounds)?, encode_block)
+}
+
 /*
  * This function should return a cipher text for a given key and plaintext
  *
  */
-pub fn encode<'a, W: Word<'a>>(key: Vec<u8>, plaintext: &'a [u8]) -> Vec<u8>
+pub fn encode<'a, W: Word<'a>>(key: Vec<u8>, plaintext: &'a [u8]) -> Result<Vec<u8>, Rc5Error>
 where
     <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
 {
-    compute::<W>(plaintext, gen_key_table(key, 12), encode_block)
+    encode_with::<W>(key, 12, plaintext)
 }
 
-fn decode_block<'a, W: Word<'a>>(ciphertext: (W, W), key_table: &[W]) -> (W, W)
+/// Like [`encode_with`], but applies `padding` first so `plaintext` no
+/// longer needs to already be a whole number of blocks.
+pub fn encode_padded<W>(
+    key: Vec<u8>,
+    rounds: u8,
+    padding: Padding,
+    plaintext: &[u8],
+) -> Result<Vec<u8>, Rc5Error>
+where
+    W: for<'a> Word<'a>,
+    for<'a> <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    let block_bytes = W::zero().count_zeros() as usize / 4;
+    let padded = padding::pad(plaintext, block_bytes, padding);
+    compute::<W>(&padded, gen_key_table(key, rounds)?, encode_block)
+}
+
+pub(crate) fn decode_block<'a, W: Word<'a>>(ciphertext: (W, W), key_table: &[W]) -> (W, W)
 where
     <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
 {
@@ -210,15 +425,46 @@ where
     )
 }
 
+/*
+ * This function should return a plaintext for a given key, round count and ciphertext
+ *
+ */
+pub fn decode_with<'a, W: Word<'a>>(
+    key: Vec<u8>,
+    rounds: u8,
+    ciphertext: &'a [u8],
+) -> Result<Vec<u8>, Rc5Error>
+where
+    <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    compute::<W>(ciphertext, gen_key_table(key, rounds)?, decode_block)
+}
+
 /*
  * This function should return a plaintext for a given key and ciphertext
  *
  */
-pub fn decode<'a, W: Word<'a>>(key: Vec<u8>, ciphertext: &'a [u8]) -> Vec<u8>
+pub fn decode<'a, W: Word<'a>>(key: Vec<u8>, ciphertext: &'a [u8]) -> Result<Vec<u8>, Rc5Error>
 where
     <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
 {
-    compute::<W>(ciphertext, gen_key_table(key, 12), decode_block)
+    decode_with::<W>(key, 12, ciphertext)
+}
+
+/// Like [`decode_with`], but validates and strips `padding` afterwards so
+/// the original, pre-padding length is recovered.
+pub fn decode_padded<W>(
+    key: Vec<u8>,
+    rounds: u8,
+    padding: Padding,
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, Rc5Error>
+where
+    W: for<'a> Word<'a>,
+    for<'a> <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+{
+    let plaintext = compute::<W>(ciphertext, gen_key_table(key, rounds)?, decode_block)?;
+    padding::unpad(plaintext, padding)
 }
 
 #[cfg(test)]
@@ -233,7 +479,7 @@ mod tests {
         ];
         let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
         let ct = vec![0x2D, 0xDC, 0x14, 0x9B, 0xCF, 0x08, 0x8B, 0x9E];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -245,7 +491,7 @@ mod tests {
         ];
         let pt = vec![0xEA, 0x02, 0x47, 0x14, 0xAD, 0x5C, 0x4D, 0x84];
         let ct = vec![0x11, 0xE4, 0x3B, 0x86, 0xD2, 0x31, 0xEA, 0x64];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -254,7 +500,7 @@ mod tests {
         let key = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
         let pt = vec![0, 0, 0, 0, 0, 0, 0, 0];
         let ct = vec![0x21, 0xA5, 0xDB, 0xEE, 0x15, 0x4B, 0x8F, 0x6D];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -266,7 +512,7 @@ mod tests {
         ];
         let pt = vec![0x21, 0xA5, 0xDB, 0xEE, 0x15, 0x4B, 0x8F, 0x6D];
         let ct = vec![0xF7, 0xC0, 0x13, 0xAC, 0x5B, 0x2B, 0x89, 0x52];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -278,7 +524,7 @@ mod tests {
         ];
         let pt = vec![0xF7, 0xC0, 0x13, 0xAC, 0x5B, 0x2B, 0x89, 0x52];
         let ct = vec![0x2F, 0x42, 0xB3, 0xB7, 0x03, 0x69, 0xFC, 0x92];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -290,7 +536,7 @@ mod tests {
         ];
         let pt = vec![0x2F, 0x42, 0xB3, 0xB7, 0x03, 0x69, 0xFC, 0x92];
         let ct = vec![0x65, 0xC1, 0x78, 0xB2, 0x84, 0xD1, 0x97, 0xCC];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -302,7 +548,7 @@ mod tests {
         ];
         let pt = vec![0x65, 0xC1, 0x78, 0xB2, 0x84, 0xD1, 0x97, 0xCC];
         let ct = vec![0xEB, 0x44, 0xE4, 0x15, 0xDA, 0x31, 0x98, 0x24];
-        let res = encode::<u32>(key, &pt);
+        let res = encode::<u32>(key, &pt).unwrap();
         assert!(ct[..] == res[..]);
     }
 
@@ -314,7 +560,7 @@ mod tests {
         ];
         let pt = vec![0x96, 0x95, 0x0D, 0xDA, 0x65, 0x4A, 0x3D, 0x62];
         let ct = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
 
@@ -326,7 +572,7 @@ mod tests {
         ];
         let pt = vec![0x63, 0x8B, 0x3A, 0x5E, 0xF7, 0x2B, 0x66, 0x3F];
         let ct = vec![0xEA, 0x02, 0x47, 0x14, 0xAD, 0x5C, 0x4D, 0x84];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
 
@@ -335,7 +581,7 @@ mod tests {
         let key = vec![0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
         let pt = vec![0, 0, 0, 0, 0, 0, 0, 0];
         let ct = vec![0x21, 0xA5, 0xDB, 0xEE, 0x15, 0x4B, 0x8F, 0x6D];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
 
@@ -347,7 +593,7 @@ mod tests {
         ];
         let pt = vec![0x21, 0xA5, 0xDB, 0xEE, 0x15, 0x4B, 0x8F, 0x6D];
         let ct = vec![0xF7, 0xC0, 0x13, 0xAC, 0x5B, 0x2B, 0x89, 0x52];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
 
@@ -359,7 +605,7 @@ mod tests {
         ];
         let pt = vec![0xF7, 0xC0, 0x13, 0xAC, 0x5B, 0x2B, 0x89, 0x52];
         let ct = vec![0x2F, 0x42, 0xB3, 0xB7, 0x03, 0x69, 0xFC, 0x92];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
 
@@ -371,7 +617,7 @@ mod tests {
         ];
         let pt = vec![0x2F, 0x42, 0xB3, 0xB7, 0x03, 0x69, 0xFC, 0x92];
         let ct = vec![0x65, 0xC1, 0x78, 0xB2, 0x84, 0xD1, 0x97, 0xCC];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
 
@@ -383,7 +629,130 @@ mod tests {
         ];
         let pt = vec![0x65, 0xC1, 0x78, 0xB2, 0x84, 0xD1, 0x97, 0xCC];
         let ct = vec![0xEB, 0x44, 0xE4, 0x15, 0xDA, 0x31, 0x98, 0x24];
-        let res = decode::<u32>(key, &ct);
+        let res = decode::<u32>(key, &ct).unwrap();
         assert!(pt[..] == res[..]);
     }
+
+    #[test]
+    fn round_trip_custom_rounds() {
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ct = encode_with::<u32>(key.clone(), 20, &pt).unwrap();
+        let res = decode_with::<u32>(key, 20, &ct).unwrap();
+        assert!(pt[..] == res[..]);
+    }
+
+    #[test]
+    fn round_trip_via_params() {
+        let params = Rc5Params {
+            word: WordSize::W32,
+            rounds: 20,
+            key: vec![
+                0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C,
+                0x0D, 0x0E, 0x0F,
+            ],
+        };
+        let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ct = params.encode(&pt).unwrap();
+        let res = params.decode(&ct).unwrap();
+        assert!(pt[..] == res[..]);
+    }
+
+    #[test]
+    fn round_trip_u16() {
+        // Independently computed (a separate Python RC5 implementation,
+        // cross-checked against the encode_a/encode_c RC5-32/12/16
+        // vectors above), so this also catches a symmetric encode/decode
+        // bug that a pure self round-trip would miss.
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let pt = vec![0x00, 0x11, 0x22, 0x33];
+        let ct = vec![0x0B, 0xDC, 0x7E, 0x8E];
+        let res = encode::<u16>(key.clone(), &pt).unwrap();
+        assert!(ct[..] == res[..]);
+        let res = decode::<u16>(key, &ct).unwrap();
+        assert!(pt[..] == res[..]);
+    }
+
+    #[test]
+    fn round_trip_u64() {
+        // Independently computed; see round_trip_u16.
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let pt = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let ct = vec![
+            0x75, 0xDA, 0x0D, 0x75, 0x00, 0x94, 0x18, 0x4E, 0x21, 0x86, 0x22, 0xC0, 0xBF, 0xC1,
+            0x6D, 0xF0,
+        ];
+        let res = encode::<u64>(key.clone(), &pt).unwrap();
+        assert!(ct[..] == res[..]);
+        let res = decode::<u64>(key, &ct).unwrap();
+        assert!(pt[..] == res[..]);
+    }
+
+    #[test]
+    fn round_trip_pkcs7_unaligned() {
+        let key = vec![0u8; 16];
+        let pt = vec![0x00, 0x11, 0x22];
+        let ct = encode_padded::<u32>(key.clone(), 12, Padding::Pkcs7, &pt).unwrap();
+        let res = decode_padded::<u32>(key, 12, Padding::Pkcs7, &ct).unwrap();
+        assert_eq!(res, pt);
+    }
+
+    #[test]
+    fn round_trip_pkcs7_aligned_adds_extra_block() {
+        let key = vec![0u8; 16];
+        let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ct = encode_padded::<u32>(key.clone(), 12, Padding::Pkcs7, &pt).unwrap();
+        assert_eq!(ct.len(), 16);
+        let res = decode_padded::<u32>(key, 12, Padding::Pkcs7, &ct).unwrap();
+        assert_eq!(res, pt);
+    }
+
+    #[test]
+    fn rc5_cipher_round_trips_and_reuses_key_table() {
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let cipher = Rc5Cipher::<u32>::new(key, 12).unwrap();
+        let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        let ct = cipher.encode(&pt).unwrap();
+        assert_eq!(cipher.decode(&ct).unwrap(), pt);
+        // A second encode with the same cipher reuses the cached key table.
+        assert_eq!(cipher.encode(&pt).unwrap(), ct);
+    }
+
+    #[test]
+    fn encode_rejects_key_too_long() {
+        let key = vec![0u8; 256];
+        let pt = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77];
+        assert_eq!(
+            encode::<u32>(key, &pt),
+            Err(Rc5Error::KeyTooLong { len: 256 })
+        );
+    }
+
+    #[test]
+    fn encode_rejects_unaligned_input() {
+        let key = vec![0u8; 16];
+        let pt = vec![0x00, 0x11, 0x22];
+        assert_eq!(
+            encode::<u32>(key, &pt),
+            Err(Rc5Error::InvalidBlockLength {
+                expected: 8,
+                got: 3
+            })
+        );
+    }
 }
@@ -0,0 +1,80 @@
+//! PKCS#7 padding, so callers can encrypt input that isn't already a whole
+//! number of blocks without managing the framing themselves.
+
+use crate::Rc5Error;
+
+/// Padding policy applied before encoding and validated/stripped after decoding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// No padding: the caller guarantees block-aligned input.
+    None,
+    /// PKCS#7: append `n` bytes each equal to `n`, where `n` is however many
+    /// bytes are needed to fill the final block (a full extra block when the
+    /// input is already aligned).
+    Pkcs7,
+}
+
+pub(crate) fn pad(data: &[u8], block_bytes: usize, padding: Padding) -> Vec<u8> {
+    match padding {
+        Padding::None => data.to_vec(),
+        Padding::Pkcs7 => {
+            let n = block_bytes - data.len() % block_bytes;
+            let mut out = Vec::with_capacity(data.len() + n);
+            out.extend_from_slice(data);
+            out.extend(vec![n as u8; n]);
+            out
+        }
+    }
+}
+
+pub(crate) fn unpad(mut data: Vec<u8>, padding: Padding) -> Result<Vec<u8>, Rc5Error> {
+    match padding {
+        Padding::None => Ok(data),
+        Padding::Pkcs7 => {
+            let n = *data.last().ok_or(Rc5Error::InvalidPadding)? as usize;
+            if n == 0 || n > data.len() || data[data.len() - n..].iter().any(|&b| b as usize != n) {
+                return Err(Rc5Error::InvalidPadding);
+            }
+            data.truncate(data.len() - n);
+            Ok(data)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkcs7_pads_unaligned_input_up_to_block_size() {
+        let padded = pad(&[0x00, 0x11, 0x22], 8, Padding::Pkcs7);
+        assert_eq!(padded, vec![0x00, 0x11, 0x22, 5, 5, 5, 5, 5]);
+    }
+
+    #[test]
+    fn pkcs7_adds_full_extra_block_when_already_aligned() {
+        let padded = pad(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77], 8, Padding::Pkcs7);
+        assert_eq!(padded.len(), 16);
+        assert_eq!(&padded[8..], &[8u8; 8]);
+    }
+
+    #[test]
+    fn pkcs7_round_trips() {
+        let data = [0x00, 0x11, 0x22];
+        let padded = pad(&data, 8, Padding::Pkcs7);
+        let unpadded = unpad(padded, Padding::Pkcs7).unwrap();
+        assert_eq!(unpadded, data);
+    }
+
+    #[test]
+    fn pkcs7_rejects_malformed_padding() {
+        let data = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 9];
+        assert_eq!(unpad(data, Padding::Pkcs7), Err(Rc5Error::InvalidPadding));
+    }
+
+    #[test]
+    fn pkcs7_rejects_zero_length_padding() {
+        let data = vec![0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0];
+        assert_eq!(unpad(data, Padding::Pkcs7), Err(Rc5Error::InvalidPadding));
+    }
+}
@@ -0,0 +1,174 @@
+//! Incremental encode/decode for input arriving in pieces, without
+//! buffering the whole message up front.
+
+use std::{convert::TryFrom, fmt::Debug};
+
+use crate::{
+    decode_block, encode_block, gen_key_table, transcode_block, FromLeBytes, Rc5Error,
+    TranscodeFn, Word,
+};
+
+/// How [`Rc5Stream::finalize`] should handle a final, not-fully-filled block.
+pub enum FlushPadding {
+    /// Require the total input length to already be block-aligned.
+    Strict,
+    /// Zero-pad the final block up to a full block before transcoding it.
+    ZeroPad,
+}
+
+/// An incremental RC5 encoder/decoder: holds the key table computed once
+/// up front and a small partial-block buffer, emitting completed blocks
+/// as `update` fills them so callers never need to hold the whole message
+/// in memory at once.
+pub struct Rc5Stream<W> {
+    key_table: Vec<W>,
+    transcode: TranscodeFn<W>,
+    block_bytes: usize,
+    buffer: Vec<u8>,
+}
+
+impl<W> Rc5Stream<W> {
+    pub fn encoder<'a>(key: Vec<u8>, rounds: u8) -> Result<Self, Rc5Error>
+    where
+        W: Word<'a>,
+        <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        Self::new(key, rounds, encode_block)
+    }
+
+    pub fn decoder<'a>(key: Vec<u8>, rounds: u8) -> Result<Self, Rc5Error>
+    where
+        W: Word<'a>,
+        <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        Self::new(key, rounds, decode_block)
+    }
+
+    fn new<'a>(key: Vec<u8>, rounds: u8, transcode: TranscodeFn<W>) -> Result<Self, Rc5Error>
+    where
+        W: Word<'a>,
+        <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        let key_table = gen_key_table::<W>(key, rounds)?;
+        let block_bytes = W::zero().count_zeros() as usize / 4;
+        Ok(Self {
+            key_table,
+            transcode,
+            block_bytes,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Feed more input into the stream, returning the bytes of whichever
+    /// blocks became complete as a result. `data` may be any length.
+    pub fn update(&mut self, data: &[u8]) -> Vec<u8>
+    where
+        W: for<'a> Word<'a>,
+        for<'a> <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        self.buffer.extend_from_slice(data);
+        let mut output = Vec::new();
+        let mut start = 0;
+        while self.buffer.len() - start >= self.block_bytes {
+            let end = start + self.block_bytes;
+            let block = self.buffer[start..end].to_vec();
+            output.extend(transcode_block(&block, &self.key_table, self.transcode));
+            start = end;
+        }
+        self.buffer.drain(..start);
+        output
+    }
+
+    /// Flush any buffered partial block per `padding` and consume the stream.
+    pub fn finalize(self, padding: FlushPadding) -> Result<Vec<u8>, Rc5Error>
+    where
+        W: for<'a> Word<'a>,
+        for<'a> <<W as FromLeBytes<'a>>::Bytes as TryFrom<&'a [u8]>>::Error: Debug,
+    {
+        if self.buffer.is_empty() {
+            return Ok(Vec::new());
+        }
+        match padding {
+            FlushPadding::Strict => Err(Rc5Error::InvalidBlockLength {
+                expected: self.block_bytes,
+                got: self.buffer.len(),
+            }),
+            FlushPadding::ZeroPad => {
+                let mut block = self.buffer;
+                block.resize(self.block_bytes, 0);
+                Ok(transcode_block(&block, &self.key_table, self.transcode))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{decode, encode};
+
+    #[test]
+    fn matches_bulk_encode_for_aligned_input() {
+        let key = vec![
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C, 0x0D,
+            0x0E, 0x0F,
+        ];
+        let pt = vec![
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0xF7, 0xC0, 0x13, 0xAC, 0x5B, 0x2B,
+            0x89, 0x52,
+        ];
+        let expected = encode::<u32>(key.clone(), &pt).unwrap();
+
+        let mut stream = Rc5Stream::<u32>::encoder(key, 12).unwrap();
+        let mut actual = Vec::new();
+        for chunk in pt.chunks(3) {
+            actual.extend(stream.update(chunk));
+        }
+        actual.extend(stream.finalize(FlushPadding::Strict).unwrap());
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn round_trips_through_encoder_and_decoder_streams() {
+        let key = vec![0u8; 16];
+        let pt = vec![0x21, 0xA5, 0xDB, 0xEE, 0x15, 0x4B, 0x8F, 0x6D];
+
+        let mut encoder = Rc5Stream::<u32>::encoder(key.clone(), 12).unwrap();
+        let mut ct = encoder.update(&pt);
+        ct.extend(encoder.finalize(FlushPadding::Strict).unwrap());
+
+        let mut decoder = Rc5Stream::<u32>::decoder(key, 12).unwrap();
+        let mut pt2 = decoder.update(&ct);
+        pt2.extend(decoder.finalize(FlushPadding::Strict).unwrap());
+        assert_eq!(pt2, pt);
+    }
+
+    #[test]
+    fn finalize_rejects_partial_block_without_padding() {
+        let mut stream = Rc5Stream::<u32>::encoder(vec![0u8; 16], 12).unwrap();
+        stream.update(&[0x00, 0x01, 0x02]);
+        assert_eq!(
+            stream.finalize(FlushPadding::Strict),
+            Err(Rc5Error::InvalidBlockLength {
+                expected: 8,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn finalize_zero_pads_partial_block() {
+        let key = vec![0u8; 16];
+        let mut stream = Rc5Stream::<u32>::encoder(key.clone(), 12).unwrap();
+        let mut ct = stream.update(&[0x00, 0x01, 0x02]);
+        ct.extend(stream.finalize(FlushPadding::ZeroPad).unwrap());
+
+        let mut padded = vec![0x00, 0x01, 0x02];
+        padded.resize(8, 0);
+        let expected = encode::<u32>(key, &padded).unwrap();
+        assert_eq!(ct, expected);
+
+        let decoded = decode::<u32>(vec![0u8; 16], &ct).unwrap();
+        assert_eq!(&decoded[..3], &[0x00, 0x01, 0x02]);
+    }
+}